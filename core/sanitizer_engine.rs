@@ -1,24 +1,199 @@
-use regex::Regex;
-use std::collections::HashMap;
+use regex::{Captures, Regex, RegexBuilder};
+use regex::bytes::{Captures as BytesCaptures, Regex as BytesRegex};
+use std::collections::BTreeMap;
 use std::ffi::{CStr, CString};
-use std::sync::OnceLock;
+use std::sync::{OnceLock, RwLock};
 use libc::c_char;
 
-static PATTERNS: OnceLock<HashMap<&'static str, Regex>> = OnceLock::new();
+/// Labels for the named capture groups in `get_pattern()` / `get_byte_pattern()`, ordered
+/// most-specific-first so that leftmost-first alternation (regex is NOT leftmost-longest) picks
+/// the longest token at a given start position, e.g. a 64-hex sha256 is claimed before the
+/// 32-hex md5 arm gets a chance at it. Index into this array lines up with the `LABEL_*` bits in
+/// a `SanitizeOptions::label_mask`.
+const LABELS: [&str; 6] = ["sha256", "md5", "jwt", "uuid", "email", "ip"];
 
-fn get_patterns() -> &'static HashMap<&'static str, Regex> {
-    PATTERNS.get_or_init(|| {
-        let mut map = HashMap::new();
-        map.insert("ip", Regex::new(r"\b(?:[0-9]{1,3}\.){3}[0-9]{1,3}\b").expect("Invalid regex"));
-        map.insert("email", Regex::new(r"\b[a-zA-Z0-9._%+-]+@[a-zA-Z0-9.-]+\.[a-zA-Z]{2,}\b").expect("Invalid regex"));
-        map.insert("uuid", Regex::new(r"\b[0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[1-5][0-9a-fA-F]{3}-[89abAB][0-9a-fA-F]{3}-[0-9a-fA-F]{12}\b").expect("Invalid regex"));
-        map.insert("sha256", Regex::new(r"\b[a-fA-F0-9]{64}\b").expect("Invalid regex"));
-        map.insert("md5", Regex::new(r"\b[a-fA-F0-9]{32}\b").expect("Invalid regex"));
-        map.insert("jwt", Regex::new(r"\beyJ[a-zA-Z0-9_-]+\.[a-zA-Z0-9_-]+\.[a-zA-Z0-9_-]+\b").expect("Invalid regex"));
-        map
-    })
+/// The regex fragment for each label in `LABELS`, without the enclosing named capture group.
+const PATTERN_ARMS: [&str; 6] = [
+    r"\b[a-fA-F0-9]{64}\b",
+    r"\b[a-fA-F0-9]{32}\b",
+    r"\beyJ[a-zA-Z0-9_-]+\.[a-zA-Z0-9_-]+\.[a-zA-Z0-9_-]+\b",
+    r"\b[0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[1-5][0-9a-fA-F]{3}-[89abAB][0-9a-fA-F]{3}-[0-9a-fA-F]{12}\b",
+    r"\b[a-zA-Z0-9._%+-]+@[a-zA-Z0-9.-]+\.[a-zA-Z]{2,}\b",
+    r"\b(?:[0-9]{1,3}\.){3}[0-9]{1,3}\b",
+];
+
+/// Bitmask flags selecting which built-in labels `sanitize_line_opts()` should redact. Index `i`
+/// of `LABELS`/`PATTERN_ARMS` corresponds to bit `1 << i`.
+pub const LABEL_SHA256: u32 = 1 << 0;
+pub const LABEL_MD5: u32 = 1 << 1;
+pub const LABEL_JWT: u32 = 1 << 2;
+pub const LABEL_UUID: u32 = 1 << 3;
+pub const LABEL_EMAIL: u32 = 1 << 4;
+pub const LABEL_IP: u32 = 1 << 5;
+pub const LABEL_ALL: u32 = LABEL_SHA256 | LABEL_MD5 | LABEL_JWT | LABEL_UUID | LABEL_EMAIL | LABEL_IP;
+
+/// Matcher option flags for `SanitizeOptions::flags`.
+pub const SANITIZE_CASE_INSENSITIVE: u32 = 1 << 0;
+pub const SANITIZE_IGNORE_WHITESPACE: u32 = 1 << 1;
+
+/// Caller-tunable knobs for `sanitize_line_opts()`, mirroring the `rure` FFI binding's options
+/// struct: a flag bitset, a label bitmask, and the `RegexBuilder` size limits that bound how
+/// large the compiled built-in alternation is allowed to grow. Custom patterns (see
+/// `add_pattern`) are compiled once at registration with the regex crate's default limits and
+/// are not bounded by these.
+#[repr(C)]
+pub struct SanitizeOptions {
+    pub flags: u32,
+    pub label_mask: u32,
+    pub size_limit: usize,
+    pub dfa_size_limit: usize,
+}
+
+/// Build the alternation pattern for the labels selected by `mask` (a bitmask over `LABELS`).
+fn alternation(mask: u32) -> String {
+    LABELS
+        .iter()
+        .zip(PATTERN_ARMS.iter())
+        .enumerate()
+        .filter(|(i, _)| mask & (1 << i) != 0)
+        .map(|(_, (label, frag))| format!("(?P<{}>{})", label, frag))
+        .collect::<Vec<_>>()
+        .join("|")
+}
+
+static PATTERN: OnceLock<Regex> = OnceLock::new();
+
+fn get_pattern() -> &'static Regex {
+    PATTERN.get_or_init(|| Regex::new(&alternation(LABEL_ALL)).expect("Invalid regex"))
+}
+
+static BYTE_PATTERN: OnceLock<BytesRegex> = OnceLock::new();
+
+fn get_byte_pattern() -> &'static BytesRegex {
+    BYTE_PATTERN.get_or_init(|| BytesRegex::new(&alternation(LABEL_ALL)).expect("Invalid regex"))
+}
+
+/// Compile the alternation for `opts.label_mask` (falling back to every label when the mask is
+/// empty or only sets bits outside `LABEL_ALL`) through a `RegexBuilder`, honoring the requested
+/// flags and size limits.
+fn build_pattern_opts(opts: &SanitizeOptions) -> Result<Regex, regex::Error> {
+    let mask = opts.label_mask & LABEL_ALL;
+    let mask = if mask == 0 { LABEL_ALL } else { mask };
+    let pattern = alternation(mask);
+    let mut builder = RegexBuilder::new(&pattern);
+    builder
+        .case_insensitive(opts.flags & SANITIZE_CASE_INSENSITIVE != 0)
+        .ignore_whitespace(opts.flags & SANITIZE_IGNORE_WHITESPACE != 0);
+    if opts.size_limit != 0 {
+        builder.size_limit(opts.size_limit);
+    }
+    if opts.dfa_size_limit != 0 {
+        builder.dfa_size_limit(opts.dfa_size_limit);
+    }
+    builder.build()
+}
+
+/// Find which named group matched in a set of captures from `get_pattern()`. Falls back to
+/// `"unknown"` rather than panicking if no named group is set, since a panic unwinding across
+/// the `extern "C"` boundary would be UB.
+fn matched_label(caps: &Captures) -> &'static str {
+    LABELS
+        .iter()
+        .copied()
+        .find(|label| caps.name(label).is_some())
+        .unwrap_or("unknown")
+}
+
+/// The `LABEL_*` bit for a label name returned by `matched_label()`.
+fn label_code(label: &str) -> u32 {
+    match label {
+        "sha256" => LABEL_SHA256,
+        "md5" => LABEL_MD5,
+        "jwt" => LABEL_JWT,
+        "uuid" => LABEL_UUID,
+        "email" => LABEL_EMAIL,
+        "ip" => LABEL_IP,
+        _ => 0,
+    }
+}
+
+/// One redacted span in the original line, modeled on `rure_match`: a byte range plus which
+/// `LABEL_*` category matched there. Returned by `find_matches()`.
+#[repr(C)]
+pub struct RedactionMatch {
+    pub start: usize,
+    pub end: usize,
+    pub label: u32,
+}
+
+/// Find which named group matched in a set of captures from `get_byte_pattern()`. Falls back to
+/// `"unknown"` rather than panicking if no named group is set, since a panic unwinding across
+/// the `extern "C"` boundary would be UB.
+fn matched_label_bytes(caps: &BytesCaptures) -> &'static str {
+    LABELS
+        .iter()
+        .copied()
+        .find(|label| caps.name(label).is_some())
+        .unwrap_or("unknown")
+}
+
+/// Operator-supplied patterns loaded via `load_patterns_from_file()` / `add_pattern()`, applied
+/// after the built-in combined pattern. Kept separate from `PATTERN` since the set of labels is
+/// only known at runtime, so it can't be folded into one compiled alternation.
+///
+/// A `BTreeMap` (ordered by label) rather than a `HashMap`, so that when two custom patterns can
+/// overlap the same text, which one "wins" is determined by label order rather than by
+/// `HashMap`'s per-process random iteration order — redaction output must be reproducible for
+/// identical input.
+static CUSTOM_PATTERNS: OnceLock<RwLock<BTreeMap<String, Regex>>> = OnceLock::new();
+
+fn custom_patterns() -> &'static RwLock<BTreeMap<String, Regex>> {
+    CUSTOM_PATTERNS.get_or_init(|| RwLock::new(BTreeMap::new()))
+}
+
+/// Byte-compiled mirror of `CUSTOM_PATTERNS`, kept in lockstep by `register_custom_pattern()` so
+/// `sanitize_bytes` redacts operator-loaded custom/glob patterns too, not just the built-ins.
+static CUSTOM_BYTE_PATTERNS: OnceLock<RwLock<BTreeMap<String, BytesRegex>>> = OnceLock::new();
+
+fn custom_byte_patterns() -> &'static RwLock<BTreeMap<String, BytesRegex>> {
+    CUSTOM_BYTE_PATTERNS.get_or_init(|| RwLock::new(BTreeMap::new()))
+}
+
+/// Compile `pattern` for both the `str` and byte engines and register it under `label` in
+/// `CUSTOM_PATTERNS`/`CUSTOM_BYTE_PATTERNS`, replacing any existing pattern with that label.
+/// Used by `add_pattern`, `add_glob_pattern`, and `load_patterns_from_file` so the two maps can
+/// never drift apart.
+fn register_custom_pattern(label: &str, pattern: &str) -> Result<(), regex::Error> {
+    let regex = Regex::new(pattern)?;
+    let byte_regex = BytesRegex::new(pattern)?;
+    custom_patterns().write().unwrap().insert(label.to_string(), regex);
+    custom_byte_patterns().write().unwrap().insert(label.to_string(), byte_regex);
+    Ok(())
+}
+
+/// Borrow a `*const c_char` argument as a `&str`, or `None` if it's null or not valid UTF-8.
+///
+/// # Safety
+/// `ptr`, if non-null, must point to a valid null-terminated C string that outlives the borrow.
+unsafe fn c_str_arg<'a>(ptr: *const c_char) -> Option<&'a str> {
+    if ptr.is_null() {
+        return None;
+    }
+    CStr::from_ptr(ptr).to_str().ok()
 }
 
+/// Sanitize a single line of text, replacing each built-in or custom match with a
+/// `[REDACTED_<LABEL>]` marker, label upper-cased (e.g. an IP address becomes `[REDACTED_IP]`).
+///
+/// Note this marker casing is a breaking change from the pre-combined-pattern baseline, which
+/// emitted the label as-is (`[REDACTED_ip]`); consumers parsing the marker text for a specific
+/// label must match on the upper-cased form. See `sanitize_line_redacts_with_uppercase_marker`
+/// below, which pins the exact text so this doesn't drift again by accident.
+///
+/// Returns null if `line` is null or isn't valid UTF-8.
+///
+/// # Safety
+/// `line` must be null, or point to a valid null-terminated C string.
 #[no_mangle]
 pub extern "C" fn sanitize_line(line: *const c_char) -> *const c_char {
     if line.is_null() {
@@ -31,12 +206,74 @@ pub extern "C" fn sanitize_line(line: *const c_char) -> *const c_char {
         Err(_) => return std::ptr::null(),
     };
 
-    let patterns = get_patterns();
+    let pattern = get_pattern();
+    let mut sanitized = pattern
+        .replace_all(input, |caps: &Captures| {
+            format!("[REDACTED_{}]", matched_label(caps).to_uppercase())
+        })
+        .into_owned();
 
-    let mut sanitized = input.to_string();
+    for (label, regex) in custom_patterns().read().unwrap().iter() {
+        sanitized = regex
+            .replace_all(&sanitized, format!("[REDACTED_{}]", label.to_uppercase()).as_str())
+            .into_owned();
+    }
 
-    for (label, regex) in patterns.iter() {
-        sanitized = regex.replace_all(&sanitized, format!("[REDACTED_{}]", label).as_str()).to_string();
+    match CString::new(sanitized) {
+        Ok(c_string) => c_string.into_raw(),
+        Err(_) => std::ptr::null(),
+    }
+}
+
+/// Sanitize a line using a caller-supplied `SanitizeOptions` instead of the fixed defaults used
+/// by `sanitize_line`: which labels are active, case-insensitive/verbose matching, and the
+/// `RegexBuilder` size limits the pattern is compiled under.
+///
+/// Unlike `sanitize_line`, the pattern here is compiled fresh per call rather than cached,
+/// since the resulting alternation depends on the supplied options. Custom patterns registered
+/// via `add_pattern` / `load_patterns_from_file` are still applied afterward, using their own
+/// compiled size.
+///
+/// Returns null if `line` or `opts` is null, the input isn't valid UTF-8, or the options fail
+/// to compile (e.g. `size_limit` too small for the selected labels).
+///
+/// # Safety
+/// `line` must be null, or point to a valid null-terminated C string. `opts` must be null, or
+/// point to a valid `SanitizeOptions`.
+#[no_mangle]
+pub extern "C" fn sanitize_line_opts(
+    line: *const c_char,
+    opts: *const SanitizeOptions,
+) -> *const c_char {
+    if line.is_null() || opts.is_null() {
+        return std::ptr::null();
+    }
+
+    let c_str = unsafe { CStr::from_ptr(line) };
+    let input = match c_str.to_str() {
+        Ok(s) => s,
+        Err(_) => return std::ptr::null(),
+    };
+
+    let opts = unsafe { &*opts };
+    let pattern = match build_pattern_opts(opts) {
+        Ok(p) => p,
+        Err(e) => {
+            eprintln!("sanitize_line_opts: failed to compile pattern: {}", e);
+            return std::ptr::null();
+        }
+    };
+
+    let mut sanitized = pattern
+        .replace_all(input, |caps: &Captures| {
+            format!("[REDACTED_{}]", matched_label(caps).to_uppercase())
+        })
+        .into_owned();
+
+    for (label, regex) in custom_patterns().read().unwrap().iter() {
+        sanitized = regex
+            .replace_all(&sanitized, format!("[REDACTED_{}]", label.to_uppercase()).as_str())
+            .into_owned();
     }
 
     match CString::new(sanitized) {
@@ -45,6 +282,297 @@ pub extern "C" fn sanitize_line(line: *const c_char) -> *const c_char {
     }
 }
 
+/// Locate every span of a built-in label in `line` without rewriting it, for building an audit
+/// trail or computing redaction statistics instead of (or in addition to) calling `sanitize_line`.
+///
+/// Runs the same built-in combined matcher as `sanitize_line` but collects `Match` spans rather
+/// than doing replacement. Custom patterns (`add_pattern`, `add_glob_pattern`,
+/// `load_patterns_from_file`) are not searched, so their spans are not reported. Writes the number of matches found to `*out_count` and returns a pointer
+/// to that many `RedactionMatch` records, which the caller must free with
+/// `free_matches(ptr, *out_count)`. Returns null if `line` or `out_count` is null, or if the
+/// input isn't valid UTF-8.
+///
+/// # Safety
+/// `line` must be null, or point to a valid null-terminated C string. `out_count` must be null,
+/// or point to a valid, writable `usize`.
+#[no_mangle]
+pub extern "C" fn find_matches(line: *const c_char, out_count: *mut usize) -> *mut RedactionMatch {
+    if line.is_null() || out_count.is_null() {
+        return std::ptr::null_mut();
+    }
+
+    let c_str = unsafe { CStr::from_ptr(line) };
+    let input = match c_str.to_str() {
+        Ok(s) => s,
+        Err(_) => return std::ptr::null_mut(),
+    };
+
+    let pattern = get_pattern();
+    let matches: Vec<RedactionMatch> = pattern
+        .captures_iter(input)
+        .map(|caps| {
+            let whole = caps.get(0).expect("the combined pattern always captures group 0");
+            RedactionMatch {
+                start: whole.start(),
+                end: whole.end(),
+                label: label_code(matched_label(&caps)),
+            }
+        })
+        .collect();
+
+    let mut boxed = matches.into_boxed_slice();
+    unsafe {
+        *out_count = boxed.len();
+    }
+    let ptr = boxed.as_mut_ptr();
+    std::mem::forget(boxed);
+    ptr
+}
+
+/// Free a match array allocated by `find_matches`.
+///
+/// # Safety
+/// `ptr` must have been returned by `find_matches` with the same `count` it reported through
+/// `out_count`, and must not be used after calling this function.
+#[no_mangle]
+pub extern "C" fn free_matches(ptr: *mut RedactionMatch, count: usize) {
+    if ptr.is_null() {
+        return;
+    }
+    unsafe {
+        drop(Vec::from_raw_parts(ptr, count, count));
+    }
+}
+
+/// Sanitize a raw byte buffer into a newly allocated one, without requiring valid UTF-8.
+///
+/// Unlike `sanitize_line`, which rejects the whole input the moment it contains an invalid
+/// UTF-8 byte, this runs the byte-oriented engine over `&[u8]` directly so log lines with
+/// latin-1 fragments, binary framing, or truncated multibyte sequences are still redacted.
+/// Operator-loaded custom/glob patterns (`add_pattern`, `add_glob_pattern`,
+/// `load_patterns_from_file`) are applied afterward via their byte-compiled mirror, the same as
+/// `sanitize_line` applies them via the `str` engine.
+///
+/// Writes the length of the returned buffer to `*out_len` and returns a pointer to it; the
+/// caller must free the buffer with `free_bytes(ptr, *out_len)`. Returns null if `ptr` or
+/// `out_len` is null.
+///
+/// # Safety
+/// `ptr` must be null, or point to at least `len` readable bytes. `out_len` must be null, or
+/// point to a valid, writable `usize`.
+#[no_mangle]
+pub extern "C" fn sanitize_bytes(ptr: *const u8, len: usize, out_len: *mut usize) -> *mut u8 {
+    if ptr.is_null() || out_len.is_null() {
+        return std::ptr::null_mut();
+    }
+
+    let input = unsafe { std::slice::from_raw_parts(ptr, len) };
+    let pattern = get_byte_pattern();
+    let mut sanitized = pattern
+        .replace_all(input, |caps: &BytesCaptures| {
+            format!("[REDACTED_{}]", matched_label_bytes(caps).to_uppercase()).into_bytes()
+        })
+        .into_owned();
+
+    for (label, regex) in custom_byte_patterns().read().unwrap().iter() {
+        sanitized = regex
+            .replace_all(&sanitized, format!("[REDACTED_{}]", label.to_uppercase()).as_bytes())
+            .into_owned();
+    }
+
+    let mut boxed = sanitized.into_boxed_slice();
+    unsafe {
+        *out_len = boxed.len();
+    }
+    let out_ptr = boxed.as_mut_ptr();
+    std::mem::forget(boxed);
+    out_ptr
+}
+
+/// Free a buffer allocated by `sanitize_bytes`.
+///
+/// # Safety
+/// `ptr` must have been returned by `sanitize_bytes` with the same `len` it reported through
+/// `out_len`, and must not be used after calling this function.
+#[no_mangle]
+pub extern "C" fn free_bytes(ptr: *mut u8, len: usize) {
+    if ptr.is_null() {
+        return;
+    }
+    unsafe {
+        drop(Vec::from_raw_parts(ptr, len, len));
+    }
+}
+
+/// Compile and register a single custom redaction pattern under `label`, replacing any existing
+/// pattern with that label.
+///
+/// Returns `0` on success, or a negative error code if `label`/`pattern` aren't valid C strings
+/// or `pattern` fails to compile.
+///
+/// # Safety
+/// `label` and `pattern` must each be null, or point to a valid null-terminated C string.
+#[no_mangle]
+pub extern "C" fn add_pattern(label: *const c_char, pattern: *const c_char) -> i32 {
+    let label = match unsafe { c_str_arg(label) } {
+        Some(s) => s,
+        None => return -1,
+    };
+    let pattern = match unsafe { c_str_arg(pattern) } {
+        Some(s) => s,
+        None => return -1,
+    };
+
+    match register_custom_pattern(label, pattern) {
+        Ok(()) => 0,
+        Err(e) => {
+            eprintln!("add_pattern: failed to compile pattern for '{}': {}", label, e);
+            -2
+        }
+    }
+}
+
+/// Translate each byte/char of a glob into its regex equivalent, in the style of Mercurial's
+/// `GLOB_REPLACEMENTS`: path-aware wildcards are substituted first (checked longest-prefix-first
+/// so `*/` and `**` aren't mistaken for a lone `*`), and everything else is escaped via
+/// `glob_escape()` so the glob's literal text can't be misread as regex syntax.
+///
+/// Wildcards are restricted to `[^/\s]` so a bare `*`/`?` can't cross a path separator *or*
+/// whitespace, keeping `*.corp.internal` from swallowing unrelated log text around a hostname.
+/// A `\b` boundary is only emitted on a side whose glob character is itself a word character;
+/// unlike an unconditional `\b{...}\b`, this doesn't fail outright on a glob like
+/// `/home/*/secrets/*` that starts and ends on a non-word character.
+fn glob_to_regex(glob: &str) -> String {
+    let chars: Vec<char> = glob.chars().collect();
+    let mut out = String::new();
+    let mut i = 0;
+    while i < chars.len() {
+        if chars[i] == '*' && chars.get(i + 1) == Some(&'/') {
+            out.push_str("(?:.*/)?");
+            i += 2;
+        } else if chars[i] == '*' && chars.get(i + 1) == Some(&'*') {
+            out.push_str(".*");
+            i += 2;
+        } else if chars[i] == '*' {
+            out.push_str(r"[^/\s]*");
+            i += 1;
+        } else if chars[i] == '?' {
+            out.push_str(r"[^/\s]");
+            i += 1;
+        } else {
+            out.push_str(&glob_escape(chars[i]));
+            i += 1;
+        }
+    }
+
+    let is_word = |c: &char| c.is_alphanumeric() || *c == '_';
+    let leading = if chars.first().is_some_and(is_word) { r"\b" } else { "" };
+    let trailing = if chars.last().is_some_and(is_word) { r"\b" } else { "" };
+    format!("{}{}{}", leading, out, trailing)
+}
+
+/// Escape a single glob character as a regex literal, via a precomputed 256-entry table for the
+/// Latin-1 range (ASCII regex metacharacters included) and `regex::escape` for anything wider.
+fn glob_escape(c: char) -> String {
+    static ESCAPE_TABLE: OnceLock<[String; 256]> = OnceLock::new();
+    let table = ESCAPE_TABLE.get_or_init(|| {
+        std::array::from_fn(|byte| regex::escape(&char::from(byte as u8).to_string()))
+    });
+    if (c as u32) < 256 {
+        table[c as usize].clone()
+    } else {
+        regex::escape(&c.to_string())
+    }
+}
+
+/// Translate a glob (e.g. `*.corp.internal`, `/home/*/secrets/*`) into a regex and register it
+/// under `label`, for scrubbing hostnames and file paths that aren't expressible as one of the
+/// fixed patterns without hand-writing raw regex.
+///
+/// Returns `0` on success, or a negative error code if `label`/`glob` aren't valid C strings or
+/// the translated regex fails to compile.
+///
+/// # Safety
+/// `label` and `glob` must each be null, or point to a valid null-terminated C string.
+#[no_mangle]
+pub extern "C" fn add_glob_pattern(label: *const c_char, glob: *const c_char) -> i32 {
+    let label = match unsafe { c_str_arg(label) } {
+        Some(s) => s,
+        None => return -1,
+    };
+    let glob = match unsafe { c_str_arg(glob) } {
+        Some(s) => s,
+        None => return -1,
+    };
+
+    let pattern = glob_to_regex(glob);
+    match register_custom_pattern(label, &pattern) {
+        Ok(()) => 0,
+        Err(e) => {
+            eprintln!("add_glob_pattern: failed to compile glob '{}' for '{}': {}", glob, label, e);
+            -2
+        }
+    }
+}
+
+/// Load custom redaction patterns from a file, one rule per line as `LABEL<whitespace>REGEX`.
+/// Blank lines and `#`-comment lines are ignored. A line whose regex fails to compile is
+/// reported and skipped rather than aborting the whole load, so one typo doesn't cost every
+/// other rule in the file.
+///
+/// Returns the number of patterns successfully loaded, or a negative error code if the file
+/// itself couldn't be read.
+///
+/// # Safety
+/// `path` must be null, or point to a valid null-terminated C string.
+#[no_mangle]
+pub extern "C" fn load_patterns_from_file(path: *const c_char) -> i32 {
+    let path = match unsafe { c_str_arg(path) } {
+        Some(s) => s,
+        None => return -1,
+    };
+
+    let contents = match std::fs::read_to_string(path) {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("load_patterns_from_file: failed to read '{}': {}", path, e);
+            return -1;
+        }
+    };
+
+    let mut loaded = 0;
+    for (lineno, raw_line) in contents.lines().enumerate() {
+        let line = raw_line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let mut parts = line.splitn(2, char::is_whitespace);
+        let label = parts.next().unwrap_or("");
+        let pattern = parts.next().map(str::trim_start).unwrap_or("");
+        if label.is_empty() || pattern.is_empty() {
+            eprintln!("load_patterns_from_file: line {}: expected 'LABEL REGEX'", lineno + 1);
+            continue;
+        }
+
+        match register_custom_pattern(label, pattern) {
+            Ok(()) => {
+                loaded += 1;
+            }
+            Err(e) => {
+                eprintln!(
+                    "load_patterns_from_file: line {}: failed to compile pattern for '{}': {}",
+                    lineno + 1,
+                    label,
+                    e
+                );
+            }
+        }
+    }
+
+    loaded
+}
+
 #[no_mangle]
 pub extern "C" fn free_string(s: *mut c_char) {
     unsafe {
@@ -52,3 +580,37 @@ pub extern "C" fn free_string(s: *mut c_char) {
         CString::from_raw(s);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn glob_to_regex_redacts_leading_slash_path() {
+        let regex = Regex::new(&glob_to_regex("/home/*/secrets/*")).expect("Invalid regex");
+        assert_eq!(
+            regex.replace_all("leak /home/alice/secrets/key here", "[REDACTED]"),
+            "leak [REDACTED] here",
+        );
+    }
+
+    #[test]
+    fn glob_to_regex_does_not_cross_whitespace() {
+        let regex = Regex::new(&glob_to_regex("*.corp.internal")).expect("Invalid regex");
+        assert_eq!(
+            regex.replace_all("ping db01.corp.internal now", "[REDACTED]"),
+            "ping [REDACTED] now",
+        );
+    }
+
+    #[test]
+    fn sanitize_line_redacts_with_uppercase_marker() {
+        let input = CString::new("reach 10.0.0.1 now").unwrap();
+        let out_ptr = sanitize_line(input.as_ptr());
+        assert!(!out_ptr.is_null());
+        let out = unsafe { CStr::from_ptr(out_ptr) }.to_str().unwrap().to_string();
+        free_string(out_ptr as *mut c_char);
+
+        assert_eq!(out, "reach [REDACTED_IP] now");
+    }
+}